@@ -0,0 +1,371 @@
+//! Dirty-tracking reactive evaluation
+//!
+//! `process()` used to be assumed to run for every node on every frame. This
+//! module adds a demand-driven evaluator on top: nodes are topologically
+//! sorted once, then walked in order, recomputing only when the node is
+//! marked dirty or the hash of its resolved inputs changed since the last
+//! pass. Everything else reuses its cached outputs, mirroring how
+//! signal/effect graphs memoize recomputation.
+//!
+//! Parameter/UI mutations don't flow through `process()`'s inputs, so they
+//! can't be captured by hashing alone; `Evaluator::set_parameter` and
+//! `Evaluator::handle_ui_action` wrap the `PluginNode` equivalents and mark
+//! the node (and its transitive downstream consumers) dirty directly.
+
+use nodle_plugin_sdk::{NodeData, ParameterChange, PluginNodeHandle, UIAction};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::signal::SampleClock;
+
+pub type NodeId = String;
+
+/// A directed connection from one node's output port to another node's
+/// input port.
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub from_node: NodeId,
+    pub from_port: String,
+    pub to_node: NodeId,
+    pub to_port: String,
+}
+
+/// Graph errors surfaced before evaluation ever runs.
+#[derive(Debug)]
+pub enum GraphError {
+    /// The node DAG contains a cycle, so no topological order exists.
+    CycleDetected,
+}
+
+impl std::fmt::Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::CycleDetected => write!(f, "node graph contains a cycle"),
+        }
+    }
+}
+
+impl std::error::Error for GraphError {}
+
+/// Cached result of the last time a node actually ran `process()`.
+struct CacheEntry {
+    input_hash: u64,
+    outputs: HashMap<String, NodeData>,
+}
+
+/// Demand-driven evaluator: walks the DAG in topological order, skipping
+/// nodes whose resolved inputs hash unchanged and that aren't otherwise
+/// dirty, reusing their cached outputs instead of calling `process()` again.
+pub struct Evaluator {
+    nodes: HashMap<NodeId, PluginNodeHandle>,
+    edges: Vec<Edge>,
+    order: Vec<NodeId>,
+    dirty: HashMap<NodeId, bool>,
+    cache: HashMap<NodeId, CacheEntry>,
+}
+
+impl Evaluator {
+    /// Build an evaluator from a node set and their connections, rejecting
+    /// cyclic graphs up front. Every node starts dirty so the first pass
+    /// always runs `process()`.
+    pub fn new(
+        nodes: HashMap<NodeId, PluginNodeHandle>,
+        edges: Vec<Edge>,
+    ) -> Result<Self, GraphError> {
+        let order = topological_sort(&nodes, &edges)?;
+        let dirty = nodes.keys().map(|id| (id.clone(), true)).collect();
+        Ok(Self {
+            nodes,
+            edges,
+            order,
+            dirty,
+            cache: HashMap::new(),
+        })
+    }
+
+    /// Set a parameter on a node and dirty it plus its transitive downstream
+    /// consumers, since a parameter change bypasses the normal input hash.
+    pub fn set_parameter(&mut self, node_id: &str, name: &str, value: NodeData) {
+        if let Some(node) = self.nodes.get_mut(node_id) {
+            node.set_parameter(name, value);
+            self.mark_dirty_transitive(node_id);
+        }
+    }
+
+    /// Forward a UI action to a node and, if it reports any parameter
+    /// changes, dirty it plus its transitive downstream consumers.
+    pub fn handle_ui_action(&mut self, node_id: &str, action: UIAction) -> Vec<ParameterChange> {
+        let Some(node) = self.nodes.get_mut(node_id) else {
+            return Vec::new();
+        };
+        let changes = node.handle_ui_action(action);
+        if !changes.is_empty() {
+            self.mark_dirty_transitive(node_id);
+        }
+        changes
+    }
+
+    /// Advance every node's `on_tick` by `dt_secs` and dirty (plus
+    /// transitively downstream) every node that reported a parameter
+    /// change, so idle/time-driven nodes (oscillators, animators, counters)
+    /// actually get re-evaluated by the next `evaluate()` instead of only
+    /// updating their own internal state.
+    pub fn tick(&mut self, dt_secs: f32) -> HashMap<NodeId, Vec<ParameterChange>> {
+        let mut changes_by_node = HashMap::new();
+        for node_id in self.order.clone() {
+            let node = self.nodes.get_mut(&node_id).expect("node in order");
+            let changes = node.on_tick(dt_secs);
+            if !changes.is_empty() {
+                self.mark_dirty_transitive(&node_id);
+                changes_by_node.insert(node_id, changes);
+            }
+        }
+        changes_by_node
+    }
+
+    /// Mark a single node dirty without propagating downstream.
+    pub fn mark_dirty(&mut self, node_id: &str) {
+        if let Some(flag) = self.dirty.get_mut(node_id) {
+            *flag = true;
+        }
+    }
+
+    /// Mark a node and every node reachable from it dirty.
+    fn mark_dirty_transitive(&mut self, node_id: &str) {
+        let mut queue = VecDeque::from([node_id.to_string()]);
+        let mut seen = HashSet::new();
+        while let Some(id) = queue.pop_front() {
+            if !seen.insert(id.clone()) {
+                continue;
+            }
+            self.mark_dirty(&id);
+            queue.extend(self.downstream_of(&id));
+        }
+    }
+
+    fn downstream_of(&self, node_id: &str) -> Vec<NodeId> {
+        self.edges
+            .iter()
+            .filter(|edge| edge.from_node == node_id)
+            .map(|edge| edge.to_node.clone())
+            .collect()
+    }
+
+    fn gather_inputs(&self, node_id: &str) -> HashMap<String, NodeData> {
+        let mut inputs = HashMap::new();
+        for edge in self.edges.iter().filter(|edge| edge.to_node == node_id) {
+            if let Some(entry) = self.cache.get(&edge.from_node) {
+                if let Some(value) = entry.outputs.get(&edge.from_port) {
+                    inputs.insert(edge.to_port.clone(), value.clone());
+                }
+            }
+        }
+        inputs
+    }
+
+    /// Walk the DAG in topological order, recomputing only the nodes that
+    /// are dirty or whose resolved inputs hash changed (unless the node
+    /// opts out of memoization via `is_pure() == false`), and return every
+    /// node's current outputs.
+    pub fn evaluate(&mut self) -> HashMap<NodeId, HashMap<String, NodeData>> {
+        for node_id in self.order.clone() {
+            let inputs = self.gather_inputs(&node_id);
+            let node = self.nodes.get_mut(&node_id).expect("node in order");
+
+            let input_hash = hash_inputs(&inputs);
+            let is_dirty = *self.dirty.get(&node_id).unwrap_or(&true);
+            let is_pure = node.is_pure();
+            let cache_hit = !is_dirty
+                && is_pure
+                && self
+                    .cache
+                    .get(&node_id)
+                    .is_some_and(|entry| entry.input_hash == input_hash);
+
+            if !cache_hit {
+                let outputs = node.process(&inputs);
+                let changed = self
+                    .cache
+                    .get(&node_id)
+                    .map(|entry| entry.outputs != outputs)
+                    .unwrap_or(true);
+                self.cache.insert(
+                    node_id.clone(),
+                    CacheEntry {
+                        input_hash,
+                        outputs,
+                    },
+                );
+                self.dirty.insert(node_id.clone(), false);
+                if changed {
+                    for downstream in self.downstream_of(&node_id) {
+                        self.mark_dirty(&downstream);
+                    }
+                }
+            }
+        }
+
+        self.cache
+            .iter()
+            .map(|(id, entry)| (id.clone(), entry.outputs.clone()))
+            .collect()
+    }
+
+    /// Walk the DAG in topological order calling `process_block` instead of
+    /// `process`, so signal/buffer nodes render a whole block at the given
+    /// clock. Block rendering always runs (it has its own per-block state,
+    /// e.g. envelope phase) so it bypasses the scalar dirty-tracking cache.
+    pub fn evaluate_block(&mut self, clock: SampleClock) -> HashMap<NodeId, HashMap<String, NodeData>> {
+        let mut block_outputs: HashMap<NodeId, HashMap<String, NodeData>> = HashMap::new();
+
+        for node_id in self.order.clone() {
+            let mut inputs = HashMap::new();
+            for edge in self.edges.iter().filter(|edge| edge.to_node == node_id) {
+                if let Some(outputs) = block_outputs.get(&edge.from_node) {
+                    if let Some(value) = outputs.get(&edge.from_port) {
+                        inputs.insert(edge.to_port.clone(), value.clone());
+                    }
+                }
+            }
+
+            let node = self.nodes.get_mut(&node_id).expect("node in order");
+            block_outputs.insert(
+                node_id,
+                node.process_block(&inputs, clock.frames, clock.sample_rate),
+            );
+        }
+
+        block_outputs
+    }
+}
+
+fn hash_inputs(inputs: &HashMap<String, NodeData>) -> u64 {
+    let mut keys: Vec<&String> = inputs.keys().collect();
+    keys.sort();
+    let mut hasher = DefaultHasher::new();
+    for key in keys {
+        key.hash(&mut hasher);
+        format!("{:?}", inputs[key]).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn topological_sort(
+    nodes: &HashMap<NodeId, PluginNodeHandle>,
+    edges: &[Edge],
+) -> Result<Vec<NodeId>, GraphError> {
+    let mut in_degree: HashMap<&NodeId, usize> = nodes.keys().map(|id| (id, 0)).collect();
+    for edge in edges {
+        if let Some(count) = in_degree.get_mut(&edge.to_node) {
+            *count += 1;
+        }
+    }
+
+    let mut queue: VecDeque<&NodeId> = in_degree
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| *id)
+        .collect();
+
+    let mut order = Vec::with_capacity(nodes.len());
+    let mut visited: HashSet<&NodeId> = HashSet::new();
+
+    while let Some(node_id) = queue.pop_front() {
+        if !visited.insert(node_id) {
+            continue;
+        }
+        order.push(node_id.clone());
+        for edge in edges.iter().filter(|edge| &edge.from_node == node_id) {
+            if let Some(count) = in_degree.get_mut(&edge.to_node) {
+                *count -= 1;
+                if *count == 0 {
+                    queue.push_back(&edge.to_node);
+                }
+            }
+        }
+    }
+
+    if order.len() != nodes.len() {
+        return Err(GraphError::CycleDetected);
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::PassthroughNode;
+    use nodle_plugin_sdk::PluginNodeHandle;
+    use std::sync::atomic::Ordering;
+
+    fn edge(from: &str, to: &str) -> Edge {
+        Edge {
+            from_node: from.to_string(),
+            from_port: "out".to_string(),
+            to_node: to.to_string(),
+            to_port: "in".to_string(),
+        }
+    }
+
+    #[test]
+    fn topological_sort_rejects_cycles() {
+        let mut nodes: HashMap<NodeId, PluginNodeHandle> = HashMap::new();
+        nodes.insert(
+            "a".to_string(),
+            PluginNodeHandle::new(Box::new(PassthroughNode::new("a", 0.0, 1.0))),
+        );
+        nodes.insert(
+            "b".to_string(),
+            PluginNodeHandle::new(Box::new(PassthroughNode::new("b", 0.0, 1.0))),
+        );
+        let edges = vec![edge("a", "b"), edge("b", "a")];
+
+        let result = Evaluator::new(nodes, edges);
+        assert!(matches!(result, Err(GraphError::CycleDetected)));
+    }
+
+    /// `a -> b -> c`, plus an unconnected sibling `d`. Dirtying `a` must
+    /// recompute exactly `{a, b, c}` on the next `evaluate()` and leave `d`
+    /// untouched — the chunk0-2 request's own invariant.
+    #[test]
+    fn parameter_change_dirties_exactly_its_transitive_downstream() {
+        let a = PassthroughNode::new("a", 1.0, 1.0);
+        let b = PassthroughNode::new("b", 0.0, 1.0);
+        let c = PassthroughNode::new("c", 0.0, 1.0);
+        let d = PassthroughNode::new("d", 0.0, 1.0);
+        let (a_calls, b_calls, c_calls, d_calls) = (
+            a.process_calls(),
+            b.process_calls(),
+            c.process_calls(),
+            d.process_calls(),
+        );
+
+        let mut nodes: HashMap<NodeId, PluginNodeHandle> = HashMap::new();
+        nodes.insert("a".to_string(), PluginNodeHandle::new(Box::new(a)));
+        nodes.insert("b".to_string(), PluginNodeHandle::new(Box::new(b)));
+        nodes.insert("c".to_string(), PluginNodeHandle::new(Box::new(c)));
+        nodes.insert("d".to_string(), PluginNodeHandle::new(Box::new(d)));
+        let edges = vec![edge("a", "b"), edge("b", "c")];
+
+        let mut evaluator = Evaluator::new(nodes, edges).expect("acyclic graph");
+        evaluator.evaluate();
+        assert_eq!(a_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(b_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(c_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(d_calls.load(Ordering::SeqCst), 1);
+
+        evaluator.set_parameter("a", "seed", NodeData::Float(5.0));
+        evaluator.evaluate();
+
+        assert_eq!(a_calls.load(Ordering::SeqCst), 2, "a was directly dirtied");
+        assert_eq!(b_calls.load(Ordering::SeqCst), 2, "b is downstream of a");
+        assert_eq!(c_calls.load(Ordering::SeqCst), 2, "c is downstream of b");
+        assert_eq!(
+            d_calls.load(Ordering::SeqCst),
+            1,
+            "d is unconnected and must not be recomputed"
+        );
+    }
+}
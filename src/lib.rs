@@ -6,6 +6,19 @@
 use nodle_plugin_sdk::*;
 use std::collections::HashMap;
 
+mod evaluation;
+mod messaging;
+mod parallel;
+mod serialization;
+mod signal;
+#[cfg(test)]
+mod test_support;
+pub use evaluation::{Edge, Evaluator, GraphError, NodeId};
+pub use messaging::{HostMessage, PluginMessage};
+pub use parallel::{ParallelEvaluator, PortKey};
+pub use serialization::{deserialize_graph, serialize_graph, NodeState, SerializationFormat};
+pub use signal::SampleClock;
+
 /// The main plugin struct
 pub struct ExamplePlugin;
 
@@ -24,17 +37,35 @@ impl NodePlugin for ExamplePlugin {
         // Register our custom nodes
         registry.register_node_factory(Box::new(HelloWorldNodeFactory)).unwrap();
         registry.register_node_factory(Box::new(MathAddNodeFactory)).unwrap();
+        registry.register_node_factory(Box::new(TickCounterNodeFactory)).unwrap();
     }
-    
+
     fn on_load(&self) -> Result<(), PluginError> {
         println!("Example Plugin loaded successfully!");
         Ok(())
     }
-    
+
     fn on_unload(&self) -> Result<(), PluginError> {
         println!("Example Plugin unloaded");
         Ok(())
     }
+
+    fn on_message(&mut self, msg: HostMessage) -> Option<PluginMessage> {
+        match msg {
+            HostMessage::ParameterSync { node_id, parameter, value } => {
+                Some(PluginMessage::ParameterSync { node_id, parameter, value })
+            }
+            HostMessage::DirtyNotification { node_id } => {
+                Some(PluginMessage::DirtyNotification { node_id })
+            }
+            HostMessage::Command { name, payload } => Some(PluginMessage::Command { name, payload }),
+        }
+    }
+
+    fn tick(&mut self, _dt_secs: f32) {
+        // The example plugin has no idle work of its own; its time-driven
+        // node (`TickCounterNode`) advances through `PluginNode::on_tick`.
+    }
 }
 
 /// Factory for HelloWorld node
@@ -153,6 +184,18 @@ impl PluginNode for HelloWorldNode {
         outputs.insert("Message".to_string(), NodeData::String(self.message.clone()));
         outputs
     }
+
+    fn serialize_state(&self) -> NodeState {
+        NodeState::new(self.id.clone(), "HelloWorld", self.position)
+            .with_parameter("message", NodeData::String(self.message.clone()))
+    }
+
+    fn deserialize_state(&mut self, state: &NodeState) {
+        self.position = Pos2::new(state.position.0, state.position.1);
+        if let Some(NodeData::String(message)) = state.parameters.get("message") {
+            self.message = message.clone();
+        }
+    }
 }
 
 /// Factory for Math Add node
@@ -169,15 +212,20 @@ impl NodeFactory for MathAddNodeFactory {
         .with_workspace_compatibility(vec!["3D", "General"])
         .with_color(Color32::from_rgb(100, 150, 255))
         .with_icon("➕")
+        // `Signal` (not `Float`) so a host's port-compatibility check lets
+        // these ports carry either a scalar `NodeData::Float` (handled by
+        // `process()`) or a streamed `NodeData::Buffer` (handled by
+        // `process_block()`) — a constant is a valid signal, just like a
+        // DC value is a valid audio-rate input on a hardware mixer.
         .with_inputs(vec![
-            PortDefinition::required("A", DataType::Float),
-            PortDefinition::required("B", DataType::Float),
+            PortDefinition::required("A", DataType::Signal),
+            PortDefinition::required("B", DataType::Signal),
         ])
         .with_outputs(vec![
-            PortDefinition::required("Result", DataType::Float)
+            PortDefinition::required("Result", DataType::Signal)
         ])
     }
-    
+
     fn create_node(&self, position: Pos2) -> PluginNodeHandle {
         PluginNodeHandle::new(Box::new(MathAddNode::new(position)))
     }
@@ -312,6 +360,202 @@ impl PluginNode for MathAddNode {
         outputs.insert("Result".to_string(), NodeData::Float(self.a + self.b));
         outputs
     }
+
+    fn process_block(
+        &mut self,
+        inputs: &HashMap<String, NodeData>,
+        frames: usize,
+        _sample_rate: f32,
+    ) -> HashMap<String, NodeData> {
+        // A sum has no notion of time, so the clock's sample rate doesn't
+        // change its output; time-driven signal nodes (oscillators,
+        // envelopes) are the ones that need it.
+        let default_a = NodeData::Float(self.a);
+        let default_b = NodeData::Float(self.b);
+        let a = inputs.get("A").unwrap_or(&default_a);
+        let b = inputs.get("B").unwrap_or(&default_b);
+
+        let mut outputs = HashMap::new();
+        outputs.insert("Result".to_string(), signal::sum_buffers(a, b, frames));
+        outputs
+    }
+
+    fn serialize_state(&self) -> NodeState {
+        NodeState::new(self.id.clone(), "PluginMathAdd", self.position)
+            .with_parameter("a", NodeData::Float(self.a))
+            .with_parameter("b", NodeData::Float(self.b))
+    }
+
+    fn deserialize_state(&mut self, state: &NodeState) {
+        self.position = Pos2::new(state.position.0, state.position.1);
+        if let Some(NodeData::Float(a)) = state.parameters.get("a") {
+            self.a = *a;
+        }
+        if let Some(NodeData::Float(b)) = state.parameters.get("b") {
+            self.b = *b;
+        }
+    }
+}
+
+/// Factory for TickCounter node
+pub struct TickCounterNodeFactory;
+
+impl NodeFactory for TickCounterNodeFactory {
+    fn metadata(&self) -> NodeMetadata {
+        NodeMetadata::new(
+            "TickCounter",
+            "Tick Counter",
+            NodeCategory::utility(),
+            "Advances a counter once per interval of elapsed time without needing an upstream input"
+        )
+        .with_workspace_compatibility(vec!["3D", "General"])
+        .with_color(Color32::from_rgb(200, 150, 100))
+        .with_icon("⏱")
+        .with_outputs(vec![
+            PortDefinition::required("Count", DataType::Float)
+        ])
+    }
+
+    fn create_node(&self, position: Pos2) -> PluginNodeHandle {
+        PluginNodeHandle::new(Box::new(TickCounterNode::new(position)))
+    }
+}
+
+/// TickCounter node implementation
+///
+/// Demonstrates `PluginNode::on_tick`: it advances purely from elapsed time
+/// rather than from a changed upstream input, and reports the change back
+/// as a `ParameterChange` so the UI stays in sync.
+pub struct TickCounterNode {
+    id: String,
+    position: Pos2,
+    count: f32,
+    seconds_per_tick: f32,
+    elapsed: f32,
+}
+
+impl TickCounterNode {
+    fn new(position: Pos2) -> Self {
+        Self {
+            id: format!("tick_counter_{}", uuid()),
+            position,
+            count: 0.0,
+            seconds_per_tick: 1.0,
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl PluginNode for TickCounterNode {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn position(&self) -> Pos2 {
+        self.position
+    }
+
+    fn set_position(&mut self, position: Pos2) {
+        self.position = position;
+    }
+
+    fn get_parameter_ui(&self) -> ParameterUI {
+        let mut elements = Vec::new();
+
+        elements.push(UIElement::Heading("Tick Counter Node".to_string()));
+        elements.push(UIElement::Separator);
+
+        elements.push(UIElement::Slider {
+            label: "Seconds Per Tick".to_string(),
+            value: self.seconds_per_tick,
+            min: 0.1,
+            max: 10.0,
+            parameter_name: "seconds_per_tick".to_string(),
+        });
+
+        elements.push(UIElement::Label(format!("Count: {}", self.count)));
+
+        ParameterUI { elements }
+    }
+
+    fn handle_ui_action(&mut self, action: UIAction) -> Vec<ParameterChange> {
+        let mut changes = Vec::new();
+
+        match action {
+            UIAction::ParameterChanged { parameter, value } => {
+                if parameter.as_str() == "seconds_per_tick" {
+                    if let Some(val) = value.as_float() {
+                        self.seconds_per_tick = val;
+                        changes.push(ParameterChange {
+                            parameter: "seconds_per_tick".to_string(),
+                            value: NodeData::Float(val),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        changes
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<NodeData> {
+        match name {
+            "seconds_per_tick" => Some(NodeData::Float(self.seconds_per_tick)),
+            "count" => Some(NodeData::Float(self.count)),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, name: &str, value: NodeData) {
+        if name == "seconds_per_tick" {
+            if let Some(val) = value.as_float() {
+                self.seconds_per_tick = val;
+            }
+        }
+    }
+
+    fn process(&mut self, _inputs: &HashMap<String, NodeData>) -> HashMap<String, NodeData> {
+        let mut outputs = HashMap::new();
+        outputs.insert("Count".to_string(), NodeData::Float(self.count));
+        outputs
+    }
+
+    fn serialize_state(&self) -> NodeState {
+        NodeState::new(self.id.clone(), "TickCounter", self.position)
+            .with_parameter("seconds_per_tick", NodeData::Float(self.seconds_per_tick))
+            .with_parameter("count", NodeData::Float(self.count))
+    }
+
+    fn deserialize_state(&mut self, state: &NodeState) {
+        self.position = Pos2::new(state.position.0, state.position.1);
+        if let Some(NodeData::Float(seconds_per_tick)) = state.parameters.get("seconds_per_tick") {
+            self.seconds_per_tick = *seconds_per_tick;
+        }
+        if let Some(NodeData::Float(count)) = state.parameters.get("count") {
+            self.count = *count;
+        }
+    }
+
+    fn on_tick(&mut self, dt_secs: f32) -> Vec<ParameterChange> {
+        self.elapsed += dt_secs;
+        // A single `tick()` call can observe an arbitrarily large gap (a
+        // stalled frame, a host that batches calls), so catch the count up
+        // fully within this call rather than only ever advancing by one.
+        let mut ticked = false;
+        while self.elapsed >= self.seconds_per_tick {
+            self.elapsed -= self.seconds_per_tick;
+            self.count += 1.0;
+            ticked = true;
+        }
+        if !ticked {
+            return Vec::new();
+        }
+        vec![ParameterChange {
+            parameter: "count".to_string(),
+            value: NodeData::Float(self.count),
+        }]
+    }
 }
 
 /// Simple UUID generation for demo purposes
@@ -0,0 +1,44 @@
+//! Host↔plugin messaging
+//!
+//! The plugin used to be purely pull-based: the host calls `on_load`,
+//! `on_unload` and `process()`, and that's it. This module adds the other
+//! half, modeled on audio-plugin host/editor messaging: a small serializable
+//! message pair the host and plugin can exchange across the existing
+//! `extern "C"` boundary, plus a per-frame `tick` so plugins can do idle
+//! work (oscillators, animators, counters) and push parameter updates to the
+//! UI without waiting for an upstream input to change.
+
+use nodle_plugin_sdk::NodeData;
+use serde::{Deserialize, Serialize};
+
+/// A message sent from the host down into a plugin.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum HostMessage {
+    /// The host's copy of a parameter changed; sync it into the plugin.
+    ParameterSync {
+        node_id: String,
+        parameter: String,
+        value: NodeData,
+    },
+    /// A node upstream of this plugin's graph became dirty.
+    DirtyNotification { node_id: String },
+    /// A named command with an arbitrary payload, for host/plugin-specific
+    /// extensions that don't warrant their own variant.
+    Command { name: String, payload: NodeData },
+}
+
+/// A message sent from a plugin back up to the host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PluginMessage {
+    /// The plugin changed one of its own parameters and the host/UI should
+    /// pick it up.
+    ParameterSync {
+        node_id: String,
+        parameter: String,
+        value: NodeData,
+    },
+    /// The named node is now dirty and should be re-evaluated.
+    DirtyNotification { node_id: String },
+    /// A named command response/event, mirroring `HostMessage::Command`.
+    Command { name: String, payload: NodeData },
+}
@@ -0,0 +1,257 @@
+//! Parallel DAG evaluation
+//!
+//! The dirty-tracking [`Evaluator`](crate::Evaluator) walks the graph one
+//! node at a time. For large graphs that leaves most cores idle, so this
+//! module partitions the topologically sorted DAG into *levels* — sets of
+//! nodes whose dependencies all live in earlier levels — and evaluates every
+//! node within a level concurrently across a bounded thread pool, joining
+//! before advancing to the next level.
+//!
+//! This requires `PluginNode: Send + Sync` (and `NodeFactory: Send + Sync`)
+//! in the SDK, since each node is handed to a worker thread as an exclusive
+//! `&mut` reference for the duration of its `process()` call.
+
+use nodle_plugin_sdk::{NodeData, PluginNodeHandle};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::evaluation::{Edge, GraphError, NodeId};
+
+/// Output of a single node's output port, addressed the way downstream
+/// consumers look it up.
+pub type PortKey = (NodeId, String);
+
+/// Evaluates a node DAG level by level, running every node in a level on a
+/// bounded thread pool and joining before the next level starts.
+pub struct ParallelEvaluator {
+    nodes: HashMap<NodeId, PluginNodeHandle>,
+    edges: Vec<Edge>,
+    levels: Vec<Vec<NodeId>>,
+    max_threads: usize,
+}
+
+impl ParallelEvaluator {
+    /// Build an evaluator from a node set and their connections, rejecting
+    /// cyclic graphs up front. `max_threads` bounds how many nodes of a
+    /// single level run concurrently; pass `1` to force sequential
+    /// execution without switching evaluators.
+    pub fn new(
+        nodes: HashMap<NodeId, PluginNodeHandle>,
+        edges: Vec<Edge>,
+        max_threads: usize,
+    ) -> Result<Self, GraphError> {
+        let levels = partition_levels(&nodes, &edges)?;
+        Ok(Self {
+            nodes,
+            edges,
+            levels,
+            max_threads: max_threads.max(1),
+        })
+    }
+
+    fn gather_inputs(
+        &self,
+        node_id: &str,
+        results: &HashMap<PortKey, NodeData>,
+    ) -> HashMap<String, NodeData> {
+        let mut inputs = HashMap::new();
+        for edge in self.edges.iter().filter(|edge| edge.to_node == node_id) {
+            let key = (edge.from_node.clone(), edge.from_port.clone());
+            if let Some(value) = results.get(&key) {
+                inputs.insert(edge.to_port.clone(), value.clone());
+            }
+        }
+        inputs
+    }
+
+    /// Evaluate every level in parallel, bounded by `max_threads`, joining
+    /// between levels.
+    pub fn evaluate(&mut self) -> HashMap<PortKey, NodeData> {
+        self.run(self.max_threads)
+    }
+
+    /// Sequential fallback: identical ordering and results, but each level
+    /// runs on the calling thread instead of a pool.
+    pub fn evaluate_sequential(&mut self) -> HashMap<PortKey, NodeData> {
+        self.run(1)
+    }
+
+    fn run(&mut self, max_threads: usize) -> HashMap<PortKey, NodeData> {
+        let mut results: HashMap<PortKey, NodeData> = HashMap::new();
+
+        for level in self.levels.clone() {
+            let inputs_by_node: HashMap<NodeId, HashMap<String, NodeData>> = level
+                .iter()
+                .map(|id| (id.clone(), self.gather_inputs(id, &results)))
+                .collect();
+
+            for chunk in level.chunks(max_threads.max(1)) {
+                // Borrow every node this chunk needs as a disjoint `&mut`
+                // up front via one `iter_mut()` pass, rather than calling
+                // `get_mut` per item inside the `map` closure below — the
+                // latter is an `FnMut` closure, and a `&mut` it produces
+                // can't be moved into a `scope.spawn` thunk that outlives
+                // the closure body.
+                let wanted: HashSet<&NodeId> = chunk.iter().collect();
+                let mut node_refs: HashMap<&NodeId, &mut PluginNodeHandle> = self
+                    .nodes
+                    .iter_mut()
+                    .filter(|(id, _)| wanted.contains(id))
+                    .collect();
+
+                let outputs = if max_threads <= 1 {
+                    chunk
+                        .iter()
+                        .map(|node_id| {
+                            let node = node_refs
+                                .remove(node_id)
+                                .expect("node present in its own level");
+                            (node_id.clone(), node.process(&inputs_by_node[node_id]))
+                        })
+                        .collect::<Vec<_>>()
+                } else {
+                    std::thread::scope(|scope| {
+                        let handles: Vec<_> = chunk
+                            .iter()
+                            .map(|node_id| {
+                                let node = node_refs
+                                    .remove(node_id)
+                                    .expect("node present in its own level");
+                                let inputs = &inputs_by_node[node_id];
+                                scope.spawn(move || (node_id.clone(), node.process(inputs)))
+                            })
+                            .collect();
+                        handles
+                            .into_iter()
+                            .map(|handle| handle.join().expect("worker thread panicked"))
+                            .collect::<Vec<_>>()
+                    })
+                };
+
+                for (node_id, node_outputs) in outputs {
+                    for (port, value) in node_outputs {
+                        results.insert((node_id.clone(), port), value);
+                    }
+                }
+            }
+        }
+
+        results
+    }
+}
+
+/// Like `topological_sort` but groups nodes into levels instead of a single
+/// flat order, so that every node in a level is independent of the others
+/// in that same level.
+fn partition_levels(
+    nodes: &HashMap<NodeId, PluginNodeHandle>,
+    edges: &[Edge],
+) -> Result<Vec<Vec<NodeId>>, GraphError> {
+    let mut in_degree: HashMap<NodeId, usize> = nodes.keys().map(|id| (id.clone(), 0)).collect();
+    for edge in edges {
+        if let Some(count) = in_degree.get_mut(&edge.to_node) {
+            *count += 1;
+        }
+    }
+
+    let mut levels = Vec::new();
+    let mut remaining: HashSet<NodeId> = nodes.keys().cloned().collect();
+    let mut frontier: VecDeque<NodeId> = in_degree
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(id, _)| id.clone())
+        .collect();
+
+    while !frontier.is_empty() {
+        let level: Vec<NodeId> = frontier.drain(..).collect();
+        for id in &level {
+            remaining.remove(id);
+        }
+
+        for id in &level {
+            for edge in edges.iter().filter(|edge| &edge.from_node == id) {
+                if let Some(count) = in_degree.get_mut(&edge.to_node) {
+                    *count -= 1;
+                    if *count == 0 {
+                        frontier.push_back(edge.to_node.clone());
+                    }
+                }
+            }
+        }
+
+        levels.push(level);
+    }
+
+    if !remaining.is_empty() {
+        return Err(GraphError::CycleDetected);
+    }
+
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::PassthroughNode;
+
+    fn edge(from: &str, to: &str) -> Edge {
+        Edge {
+            from_node: from.to_string(),
+            from_port: "out".to_string(),
+            to_node: to.to_string(),
+            to_port: "in".to_string(),
+        }
+    }
+
+    fn node_set(ids: &[&str]) -> HashMap<NodeId, PluginNodeHandle> {
+        ids.iter()
+            .map(|id| {
+                (
+                    id.to_string(),
+                    PluginNodeHandle::new(Box::new(PassthroughNode::new(*id, 0.0, 1.0))),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn partition_levels_rejects_cycles() {
+        let nodes = node_set(&["a", "b"]);
+        let edges = vec![edge("a", "b"), edge("b", "a")];
+
+        let result = ParallelEvaluator::new(nodes, edges, 4);
+        assert!(matches!(result, Err(GraphError::CycleDetected)));
+    }
+
+    /// Diamond `a -> {b, c} -> d`: `b` and `c` are mutually independent and
+    /// must land in the same level, between `a`'s level and `d`'s.
+    #[test]
+    fn partition_levels_groups_independent_nodes_together() {
+        let nodes = node_set(&["a", "b", "c", "d"]);
+        let edges = vec![edge("a", "b"), edge("a", "c"), edge("b", "d"), edge("c", "d")];
+
+        let levels = partition_levels(&nodes, &edges).expect("acyclic graph");
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0], vec!["a".to_string()]);
+        let mut middle = levels[1].clone();
+        middle.sort();
+        assert_eq!(middle, vec!["b".to_string(), "c".to_string()]);
+        assert_eq!(levels[2], vec!["d".to_string()]);
+    }
+
+    #[test]
+    fn evaluate_matches_evaluate_sequential() {
+        let nodes = node_set(&["a", "b", "c"]);
+        let edges = vec![edge("a", "b"), edge("b", "c")];
+
+        let mut parallel_eval = ParallelEvaluator::new(nodes, edges, 4).expect("acyclic graph");
+        let parallel_results = parallel_eval.evaluate();
+
+        let nodes = node_set(&["a", "b", "c"]);
+        let edges = vec![edge("a", "b"), edge("b", "c")];
+        let mut sequential_eval = ParallelEvaluator::new(nodes, edges, 1).expect("acyclic graph");
+        let sequential_results = sequential_eval.evaluate_sequential();
+
+        assert_eq!(parallel_results, sequential_results);
+    }
+}
@@ -0,0 +1,170 @@
+//! Graph serialization
+//!
+//! Gives nodes a format-agnostic way to persist their full state (parameters,
+//! id, position) and restore it later. `NodeState` is the wire-neutral
+//! intermediate representation; `SerializationFormat` picks how a batch of
+//! `NodeState`s is actually encoded to bytes.
+//!
+//! Compact binary formats (`Bincode`, `Cbor`) are meant for runtime
+//! autosaves, while the human-diffable formats (`Ron`, `Json`) are meant for
+//! project files that get checked into version control.
+
+// `NodeData` derives `Serialize`/`Deserialize` with a stable `#[serde(tag = "type")]`
+// in the SDK itself, so new variants added there don't break existing save files.
+use nodle_plugin_sdk::{NodeData, Pos2};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Format-agnostic snapshot of a single node's persisted state.
+///
+/// This is what `PluginNode::serialize_state`/`deserialize_state` read and
+/// write; the `SerializationFormat` variants only control how a `Vec<NodeState>`
+/// is turned into bytes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeState {
+    /// Stable node id, as returned by `PluginNode::id`.
+    pub id: String,
+    /// Node type tag, used to pick the right `NodeFactory` on load.
+    pub node_type: String,
+    pub position: (f32, f32),
+    pub parameters: HashMap<String, NodeData>,
+}
+
+impl NodeState {
+    pub fn new(id: impl Into<String>, node_type: impl Into<String>, position: Pos2) -> Self {
+        Self {
+            id: id.into(),
+            node_type: node_type.into(),
+            position: (position.x, position.y),
+            parameters: HashMap::new(),
+        }
+    }
+
+    pub fn with_parameter(mut self, name: impl Into<String>, value: NodeData) -> Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+}
+
+/// Wire format used to encode/decode a graph's `NodeState`s.
+///
+/// `Bincode`/`Cbor` are compact binaries for runtime saves; `Ron`/`Json` are
+/// human-diffable and intended for project files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SerializationFormat {
+    Bincode,
+    Ron,
+    Cbor,
+    Json,
+}
+
+/// Errors produced while encoding or decoding a graph.
+#[derive(Debug)]
+pub enum SerializationError {
+    Encode(String),
+    Decode(String),
+}
+
+impl std::fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SerializationError::Encode(msg) => write!(f, "failed to encode graph: {msg}"),
+            SerializationError::Decode(msg) => write!(f, "failed to decode graph: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SerializationError {}
+
+/// Encode a graph's node states into bytes using the given format.
+pub fn serialize_graph(
+    nodes: &[NodeState],
+    format: SerializationFormat,
+) -> Result<Vec<u8>, SerializationError> {
+    match format {
+        SerializationFormat::Bincode => {
+            bincode::serialize(nodes).map_err(|e| SerializationError::Encode(e.to_string()))
+        }
+        SerializationFormat::Cbor => {
+            let mut bytes = Vec::new();
+            ciborium::into_writer(nodes, &mut bytes)
+                .map_err(|e| SerializationError::Encode(e.to_string()))?;
+            Ok(bytes)
+        }
+        SerializationFormat::Ron => ron::ser::to_string_pretty(nodes, Default::default())
+            .map(|s| s.into_bytes())
+            .map_err(|e| SerializationError::Encode(e.to_string())),
+        SerializationFormat::Json => serde_json::to_vec_pretty(nodes)
+            .map_err(|e| SerializationError::Encode(e.to_string())),
+    }
+}
+
+/// Decode a graph's node states from bytes previously produced by
+/// `serialize_graph` with the same format.
+pub fn deserialize_graph(
+    bytes: &[u8],
+    format: SerializationFormat,
+) -> Result<Vec<NodeState>, SerializationError> {
+    match format {
+        SerializationFormat::Bincode => {
+            bincode::deserialize(bytes).map_err(|e| SerializationError::Decode(e.to_string()))
+        }
+        SerializationFormat::Cbor => ciborium::from_reader(bytes)
+            .map_err(|e| SerializationError::Decode(e.to_string())),
+        SerializationFormat::Ron => ron::de::from_bytes(bytes)
+            .map_err(|e| SerializationError::Decode(e.to_string())),
+        SerializationFormat::Json => {
+            serde_json::from_slice(bytes).map_err(|e| SerializationError::Decode(e.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> Vec<NodeState> {
+        vec![
+            NodeState::new("hello_world_1", "HelloWorld", Pos2::new(10.0, 20.0))
+                .with_parameter("message", NodeData::String("hi".to_string())),
+            NodeState::new("math_add_1", "PluginMathAdd", Pos2::new(-5.0, 0.0))
+                .with_parameter("a", NodeData::Float(1.5))
+                .with_parameter("b", NodeData::Float(2.5)),
+        ]
+    }
+
+    fn assert_round_trips(format: SerializationFormat) {
+        let original = sample_graph();
+        let bytes = serialize_graph(&original, format).expect("serialize_graph should succeed");
+        let restored =
+            deserialize_graph(&bytes, format).expect("deserialize_graph should succeed");
+
+        assert_eq!(restored.len(), original.len());
+        for (a, b) in original.iter().zip(restored.iter()) {
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.node_type, b.node_type);
+            assert_eq!(a.position, b.position);
+            assert_eq!(a.parameters, b.parameters);
+        }
+    }
+
+    #[test]
+    fn round_trips_bincode() {
+        assert_round_trips(SerializationFormat::Bincode);
+    }
+
+    #[test]
+    fn round_trips_cbor() {
+        assert_round_trips(SerializationFormat::Cbor);
+    }
+
+    #[test]
+    fn round_trips_ron() {
+        assert_round_trips(SerializationFormat::Ron);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        assert_round_trips(SerializationFormat::Json);
+    }
+}
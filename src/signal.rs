@@ -0,0 +1,61 @@
+//! Block-based signal processing
+//!
+//! `NodeData` used to carry only scalar `Float`/`String`, so a node could
+//! only pass a single value per evaluation. The SDK now also has
+//! `NodeData::Buffer(Vec<f32>)` (`DataType::Signal` on the port side) so DSP
+//! style nodes — mixers, gain, filters, envelope generators — can operate on
+//! whole sample blocks at once, the same way audio plugin SDKs hand a node a
+//! frame count and ask it to render a block. `PluginNode::process_block`
+//! defaults to calling scalar `process()` once per frame; nodes that care
+//! about throughput override it to work on buffers directly.
+//!
+//! [`SampleClock`] is passed to `Evaluator::evaluate_block` so block-processing
+//! nodes know the sample rate they're rendering at, not just the frame
+//! count of the current block.
+
+use nodle_plugin_sdk::NodeData;
+
+/// The clock a block-processing evaluation pass runs at: how many samples
+/// per second, and how many frames are in the current block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampleClock {
+    pub sample_rate: f32,
+    pub frames: usize,
+}
+
+impl SampleClock {
+    pub fn new(sample_rate: f32, frames: usize) -> Self {
+        Self {
+            sample_rate,
+            frames,
+        }
+    }
+
+    /// Duration of the current block in seconds.
+    pub fn block_duration_secs(&self) -> f32 {
+        self.frames as f32 / self.sample_rate
+    }
+}
+
+/// Sum two signal buffers element-wise, rendering exactly `frames` samples.
+/// A scalar (or missing) operand is broadcast across the whole block rather
+/// than treated as a single sample padded with silence, matching how a
+/// constant input behaves in the non-block `process()` path.
+pub fn sum_buffers(a: &NodeData, b: &NodeData, frames: usize) -> NodeData {
+    let a = as_buffer(a, frames);
+    let b = as_buffer(b, frames);
+    let out = (0..frames).map(|i| a[i] + b[i]).collect();
+    NodeData::Buffer(out)
+}
+
+fn as_buffer(data: &NodeData, frames: usize) -> Vec<f32> {
+    match data {
+        NodeData::Buffer(samples) => {
+            let mut samples = samples.clone();
+            samples.resize(frames, 0.0);
+            samples
+        }
+        NodeData::Float(value) => vec![*value; frames],
+        _ => vec![0.0; frames],
+    }
+}
@@ -0,0 +1,104 @@
+//! Shared test doubles for the evaluator/parallel-evaluator test suites.
+//!
+//! `PluginNode` pulls in enough SDK surface (UI, parameters, FFI-safe
+//! handles) that hand-writing one from scratch in every test module would
+//! just be copy-pasted boilerplate, so the minimal passthrough node used by
+//! both `evaluation::tests` and `parallel::tests` lives here instead.
+
+use crate::NodeState;
+use nodle_plugin_sdk::{
+    NodeData, ParameterChange, ParameterUI, Pos2, UIAction,
+};
+use nodle_plugin_sdk::PluginNode;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A node that reads a single `"in"` input (defaulting to its `seed`
+/// parameter when unconnected), adds `increment`, and writes the result to
+/// `"out"`. Counts how many times `process()` actually ran, so dirty/cache
+/// behavior can be asserted on directly.
+pub struct PassthroughNode {
+    pub id: String,
+    pub position: Pos2,
+    pub seed: f32,
+    pub increment: f32,
+    pub process_calls: Arc<AtomicUsize>,
+}
+
+impl PassthroughNode {
+    pub fn new(id: impl Into<String>, seed: f32, increment: f32) -> Self {
+        Self {
+            id: id.into(),
+            position: Pos2::new(0.0, 0.0),
+            seed,
+            increment,
+            process_calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn process_calls(&self) -> Arc<AtomicUsize> {
+        self.process_calls.clone()
+    }
+}
+
+impl PluginNode for PassthroughNode {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn position(&self) -> Pos2 {
+        self.position
+    }
+
+    fn set_position(&mut self, position: Pos2) {
+        self.position = position;
+    }
+
+    fn get_parameter_ui(&self) -> ParameterUI {
+        ParameterUI { elements: Vec::new() }
+    }
+
+    fn handle_ui_action(&mut self, _action: UIAction) -> Vec<ParameterChange> {
+        Vec::new()
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<NodeData> {
+        match name {
+            "seed" => Some(NodeData::Float(self.seed)),
+            _ => None,
+        }
+    }
+
+    fn set_parameter(&mut self, name: &str, value: NodeData) {
+        if name == "seed" {
+            if let Some(val) = value.as_float() {
+                self.seed = val;
+            }
+        }
+    }
+
+    fn process(&mut self, inputs: &HashMap<String, NodeData>) -> HashMap<String, NodeData> {
+        self.process_calls.fetch_add(1, Ordering::SeqCst);
+        let input = match inputs.get("in") {
+            Some(NodeData::Float(value)) => *value,
+            _ => self.seed,
+        };
+
+        let mut outputs = HashMap::new();
+        outputs.insert("out".to_string(), NodeData::Float(input + self.increment));
+        outputs
+    }
+
+    fn serialize_state(&self) -> NodeState {
+        NodeState::new(self.id.clone(), "Passthrough", self.position)
+            .with_parameter("seed", NodeData::Float(self.seed))
+    }
+
+    fn deserialize_state(&mut self, state: &NodeState) {
+        self.position = Pos2::new(state.position.0, state.position.1);
+        if let Some(NodeData::Float(seed)) = state.parameters.get("seed") {
+            self.seed = *seed;
+        }
+    }
+}